@@ -1,14 +1,15 @@
 use std::io::{self, BufRead};
 use uuid::Uuid;
 use chrono::{TimeZone, Utc};
+use chrono_tz::Tz;
 use clap::Parser;
 use serde::Serialize;
 
-/// Extract timestamp from UUID version 7
+/// Extract timestamp from time-based UUIDs (v1, v6, v7)
 #[derive(Parser, Debug)]
 #[command(name = "uuid7time")]
 #[command(version)]
-#[command(about = "Extract timestamps from UUID version 7", long_about = None)]
+#[command(about = "Extract timestamps from time-based UUIDs (v1, v6, v7)", long_about = None)]
 struct Cli {
     /// UUID(s) to extract timestamp from
     #[arg(value_name = "UUID")]
@@ -29,7 +30,36 @@ struct Cli {
     /// Output JSON format (shortcut for --format json)
     #[arg(short = 'j', long, conflicts_with = "format")]
     json: bool,
-    
+
+    /// IANA timezone to format the timestamp in (e.g. America/New_York). Defaults to UTC.
+    #[arg(short = 'z', long, value_name = "TZ")]
+    timezone: Option<String>,
+
+    /// Format the timestamp with a custom strftime pattern (e.g. "%Y-%m-%d %H:%M:%S%.3f")
+    #[arg(long, value_name = "PATTERN", conflicts_with = "format")]
+    strftime: Option<String>,
+
+    /// Generate a v7 UUID encoding the given time instead of reading one. Accepts unix
+    /// seconds, unix milliseconds, or an RFC3339 timestamp.
+    #[arg(short = 'g', long, value_name = "WHEN")]
+    generate: Option<String>,
+
+    /// Report version, variant, and validity instead of the timestamp
+    #[arg(long)]
+    inspect: bool,
+
+    /// Buffer all input UUIDs and print them in chronological order by timestamp
+    #[arg(long)]
+    sort: bool,
+
+    /// Reverse the order produced by --sort
+    #[arg(long, requires = "sort")]
+    reverse: bool,
+
+    /// Include a human-readable age relative to now (e.g. "3h ago", "in 2m")
+    #[arg(long)]
+    relative: bool,
+
     /// Suppress error messages
     #[arg(short, long)]
     quiet: bool,
@@ -41,11 +71,14 @@ enum OutputFormat {
     Unix,
     UnixMs,
     Json,
+    Strftime(String),
 }
 
 impl OutputFormat {
     fn from_cli(cli: &Cli) -> Result<Self, String> {
-        if cli.unix {
+        if let Some(pattern) = &cli.strftime {
+            Ok(OutputFormat::Strftime(pattern.clone()))
+        } else if cli.unix {
             Ok(OutputFormat::Unix)
         } else if cli.unix_ms {
             Ok(OutputFormat::UnixMs)
@@ -70,35 +103,273 @@ struct JsonOutput {
     timestamp_sec: i64,
     iso8601: String,
     rfc3339: String,
+    timezone: String,
+    local_rfc3339: String,
+    relative: Option<String>,
 }
 
-/// Extract timestamp in milliseconds from UUID v7
-fn extract_timestamp_ms(uuid: &Uuid) -> Result<i64, String> {
+/// Render the age of `ts_ms` relative to now, e.g. "3h ago" or "in 2m"
+fn humanize_relative(ts_ms: i64) -> String {
+    let diff_ms = Utc::now().timestamp_millis() - ts_ms;
+    let diff_sec = diff_ms.abs() / 1000;
+
+    let (value, unit) = if diff_sec < 60 {
+        (diff_sec, "s")
+    } else if diff_sec < 3600 {
+        (diff_sec / 60, "m")
+    } else if diff_sec < 86_400 {
+        (diff_sec / 3600, "h")
+    } else {
+        (diff_sec / 86_400, "d")
+    };
+
+    if diff_ms >= 0 {
+        format!("{}{} ago", value, unit)
+    } else {
+        format!("in {}{}", value, unit)
+    }
+}
+
+/// Resolve the `--timezone` option to a `Tz`, defaulting to UTC
+fn resolve_timezone(cli: &Cli) -> Result<Tz, String> {
+    match &cli.timezone {
+        None => Ok(chrono_tz::UTC),
+        Some(name) => name
+            .parse::<Tz>()
+            .map_err(|_| format!("Unknown timezone: {}. Use an IANA tz-database name (e.g. America/New_York)", name)),
+    }
+}
+
+/// Number of 100ns intervals between the Gregorian epoch (1582-10-15) and the Unix epoch
+const GREGORIAN_TO_UNIX_100NS: u64 = 0x01B21DD213814000;
+
+/// Convert a 60-bit Gregorian-epoch 100ns count to Unix milliseconds
+///
+/// Done in `i128` because the Gregorian epoch predates 1970, so a legitimately
+/// representable v1/v6 timestamp can land before the Unix epoch and go negative.
+fn ts_100ns_to_unix_ms(ts_100ns: u64) -> Result<i64, String> {
+    let ms = (ts_100ns as i128 - GREGORIAN_TO_UNIX_100NS as i128) / 10_000;
+    i64::try_from(ms).map_err(|_| "Timestamp out of range".to_string())
+}
+
+/// Extract timestamp in milliseconds from a v7 UUID
+fn extract_timestamp_ms_v7(uuid: &Uuid) -> Result<i64, String> {
     // UUIDv7: first 6 bytes = 48-bit timestamp in milliseconds
     let bytes = uuid.as_bytes();
     let ts_ms = u64::from_be_bytes([
         0, 0,
         bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5]
     ]);
-    
+
     Ok(ts_ms as i64)
 }
 
-/// Format timestamp according to specified output format
-fn format_timestamp(uuid_str: &str, ts_ms: i64, format: &OutputFormat) -> Result<String, String> {
+/// Extract timestamp in milliseconds from a v1 UUID
+///
+/// The 60-bit count of 100ns intervals since the Gregorian epoch is scattered
+/// across `time_low` (bytes 0..4), `time_mid` (bytes 4..6), and the lower 12
+/// bits of `time_hi_and_version` (bytes 6..8).
+fn extract_timestamp_ms_v1(uuid: &Uuid) -> Result<i64, String> {
+    let bytes = uuid.as_bytes();
+    let time_low = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+    let time_mid = u16::from_be_bytes([bytes[4], bytes[5]]) as u64;
+    let time_hi = (u16::from_be_bytes([bytes[6], bytes[7]]) & 0x0FFF) as u64;
+
+    let ts_100ns = (time_hi << 48) | (time_mid << 32) | time_low;
+    ts_100ns_to_unix_ms(ts_100ns)
+}
+
+/// Extract timestamp in milliseconds from a v6 UUID
+///
+/// Same 60-bit field as v1, but reordered so the timestamp sorts
+/// lexicographically: the 32 most-significant bits come first, then the
+/// middle 16 bits, then the low 12 bits of `time_hi_and_version`.
+fn extract_timestamp_ms_v6(uuid: &Uuid) -> Result<i64, String> {
+    let bytes = uuid.as_bytes();
+    let high = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+    let mid = u16::from_be_bytes([bytes[4], bytes[5]]) as u64;
+    let low = (u16::from_be_bytes([bytes[6], bytes[7]]) & 0x0FFF) as u64;
+
+    let ts_100ns = (high << 28) | (mid << 12) | low;
+    ts_100ns_to_unix_ms(ts_100ns)
+}
+
+/// Extract timestamp in milliseconds from a time-based UUID (v1, v6, or v7)
+fn extract_timestamp_ms(uuid: &Uuid) -> Result<i64, String> {
+    match uuid.get_version_num() {
+        1 => extract_timestamp_ms_v1(uuid),
+        6 => extract_timestamp_ms_v6(uuid),
+        7 => extract_timestamp_ms_v7(uuid),
+        v => Err(format!(
+            "UUID version {} does not carry a timestamp (only v1, v6, v7 are supported)",
+            v
+        )),
+    }
+}
+
+/// Parse a `--generate` input as unix seconds, unix milliseconds, or RFC3339
+fn parse_when(input: &str) -> Result<i64, String> {
+    let trimmed = input.trim();
+
+    if let Ok(n) = trimmed.parse::<i64>() {
+        // Anything with magnitude below 10^12 can't plausibly be a millisecond
+        // timestamp (that threshold is itself the year 2001 in ms), so treat
+        // it as seconds; otherwise it's already milliseconds.
+        return Ok(if n.abs() >= 1_000_000_000_000 { n } else { n * 1000 });
+    }
+
+    chrono::DateTime::parse_from_rfc3339(trimmed)
+        .map(|dt| dt.timestamp_millis())
+        .map_err(|_| format!(
+            "Invalid timestamp '{}'. Use unix seconds, unix milliseconds, or RFC3339",
+            trimmed
+        ))
+}
+
+/// Mint a v7 UUID whose first 48 bits encode `ts_ms`, with the rest random
+fn generate_uuid_v7(ts_ms: i64) -> Result<Uuid, String> {
+    if ts_ms < 0 {
+        return Err("Timestamp must not be before the Unix epoch".to_string());
+    }
+    if ts_ms >= 1 << 48 {
+        return Err("Timestamp does not fit in the 48-bit UUIDv7 timestamp field".to_string());
+    }
+
+    let ts_bytes = (ts_ms as u64).to_be_bytes();
+    let random_bytes: [u8; 10] = rand::random();
+
+    let mut bytes = [0u8; 16];
+    bytes[0..6].copy_from_slice(&ts_bytes[2..8]);
+    bytes[6..16].copy_from_slice(&random_bytes);
+    bytes[6] = (bytes[6] & 0x0F) | 0x70; // version 7
+    bytes[8] = (bytes[8] & 0x3F) | 0x80; // variant RFC 4122 (10xxxxxx)
+
+    Ok(Uuid::from_bytes(bytes))
+}
+
+#[derive(Serialize)]
+struct GenerateOutput {
+    uuid: String,
+    timestamp_ms: i64,
+    iso8601: String,
+}
+
+/// Generate a v7 UUID for `when`, rendering it as plain text or JSON
+fn run_generate(when: &str, format: &OutputFormat) -> Result<String, String> {
+    let ts_ms = parse_when(when)?;
+    let uuid = generate_uuid_v7(ts_ms)?;
+
+    if *format == OutputFormat::Json {
+        let dt = Utc.timestamp_millis_opt(ts_ms)
+            .single()
+            .ok_or_else(|| "Timestamp out of range".to_string())?;
+        let output = GenerateOutput {
+            uuid: uuid.to_string(),
+            timestamp_ms: ts_ms,
+            iso8601: dt.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        };
+        serde_json::to_string(&output)
+            .map_err(|e| format!("JSON serialization error: {}", e))
+    } else {
+        Ok(uuid.to_string())
+    }
+}
+
+/// Earliest plausible v7 timestamp: 2020-01-01T00:00:00Z
+const MIN_PLAUSIBLE_V7_MS: i64 = 1_577_836_800_000;
+
+/// How far past "now" a v7 timestamp may be and still look plausible
+const PLAUSIBLE_V7_FUTURE_SLACK_MS: i64 = 365 * 24 * 60 * 60 * 1000;
+
+/// Sanity-check a v7 timestamp: after 2020 and not far in the future
+fn is_plausible_v7_timestamp(ts_ms: i64) -> bool {
+    ts_ms >= MIN_PLAUSIBLE_V7_MS && ts_ms <= Utc::now().timestamp_millis() + PLAUSIBLE_V7_FUTURE_SLACK_MS
+}
+
+/// Human-readable name for a UUID variant
+fn variant_name(uuid: &Uuid) -> &'static str {
+    match uuid.get_variant() {
+        uuid::Variant::NCS => "NCS (reserved, backward compatibility)",
+        uuid::Variant::RFC4122 => "RFC 4122",
+        uuid::Variant::Microsoft => "Microsoft (reserved, backward compatibility)",
+        uuid::Variant::Future => "Future/reserved",
+        _ => "Unknown",
+    }
+}
+
+#[derive(Serialize)]
+struct InspectOutput {
+    uuid: String,
+    version: usize,
+    variant: String,
+    is_time_based: bool,
+    plausible: Option<bool>,
+}
+
+/// Report structural metadata for a UUID: version, variant, and (for v7) plausibility
+fn inspect_uuid(uuid_str: &str, format: &OutputFormat) -> Result<String, String> {
+    let uuid = Uuid::parse_str(uuid_str.trim())
+        .map_err(|e| format!("Invalid UUID: {}", e))?;
+
+    let version = uuid.get_version_num();
+    let is_time_based = matches!(version, 1 | 6 | 7);
+    let plausible = if version == 7 {
+        extract_timestamp_ms_v7(&uuid).ok().map(is_plausible_v7_timestamp)
+    } else {
+        None
+    };
+
+    if *format == OutputFormat::Json {
+        let output = InspectOutput {
+            uuid: uuid_str.trim().to_string(),
+            version,
+            variant: variant_name(&uuid).to_string(),
+            is_time_based,
+            plausible,
+        };
+        serde_json::to_string(&output)
+            .map_err(|e| format!("JSON serialization error: {}", e))
+    } else {
+        let mut line = format!(
+            "{} version={} variant={} time_based={}",
+            uuid_str.trim(), version, variant_name(&uuid), is_time_based
+        );
+        if let Some(p) = plausible {
+            line.push_str(&format!(" plausible={}", p));
+        }
+        Ok(line)
+    }
+}
+
+/// Format timestamp according to specified output format, localized into `tz`
+fn format_timestamp(uuid_str: &str, ts_ms: i64, format: &OutputFormat, tz: Tz, relative: bool) -> Result<String, String> {
     let dt = Utc.timestamp_millis_opt(ts_ms)
         .single()
         .ok_or_else(|| "Timestamp out of range".to_string())?;
-    
+    let local_dt = dt.with_timezone(&tz);
+    let relative_str = relative.then(|| humanize_relative(ts_ms));
+
+    let with_relative = |value: String| match &relative_str {
+        Some(r) => format!("{}\t{}", value, r),
+        None => value,
+    };
+
     match format {
         OutputFormat::Iso => {
-            Ok(dt.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+            Ok(with_relative(local_dt.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)))
         },
         OutputFormat::Unix => {
-            Ok((ts_ms / 1000).to_string())
+            Ok(with_relative((ts_ms / 1000).to_string()))
         },
         OutputFormat::UnixMs => {
-            Ok(ts_ms.to_string())
+            Ok(with_relative(ts_ms.to_string()))
+        },
+        OutputFormat::Strftime(pattern) => {
+            let items: Vec<_> = chrono::format::StrftimeItems::new(pattern).collect();
+            if items.iter().any(|item| matches!(item, chrono::format::Item::Error)) {
+                return Err(format!("Invalid strftime pattern: {}", pattern));
+            }
+            Ok(with_relative(local_dt.format(pattern).to_string()))
         },
         OutputFormat::Json => {
             let output = JsonOutput {
@@ -107,6 +378,9 @@ fn format_timestamp(uuid_str: &str, ts_ms: i64, format: &OutputFormat) -> Result
                 timestamp_sec: ts_ms / 1000,
                 iso8601: dt.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
                 rfc3339: dt.to_rfc3339_opts(chrono::SecondsFormat::Millis, false),
+                timezone: tz.to_string(),
+                local_rfc3339: local_dt.to_rfc3339_opts(chrono::SecondsFormat::Millis, false),
+                relative: relative_str,
             };
             serde_json::to_string(&output)
                 .map_err(|e| format!("JSON serialization error: {}", e))
@@ -115,12 +389,12 @@ fn format_timestamp(uuid_str: &str, ts_ms: i64, format: &OutputFormat) -> Result
 }
 
 /// Process a single UUID
-fn process_uuid(uuid_str: &str, format: &OutputFormat) -> Result<String, String> {
+fn process_uuid(uuid_str: &str, format: &OutputFormat, tz: Tz, relative: bool) -> Result<String, String> {
     let uuid = Uuid::parse_str(uuid_str.trim())
         .map_err(|e| format!("Invalid UUID: {}", e))?;
-    
+
     let ts_ms = extract_timestamp_ms(&uuid)?;
-    format_timestamp(uuid_str.trim(), ts_ms, format)
+    format_timestamp(uuid_str.trim(), ts_ms, format, tz, relative)
 }
 
 fn main() {
@@ -134,7 +408,32 @@ fn main() {
             std::process::exit(1);
         }
     };
-    
+
+    // Resolve the timezone to format output in
+    let tz = match resolve_timezone(&cli) {
+        Ok(tz) => tz,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Reverse mode: mint a UUID instead of reading one
+    if let Some(when) = &cli.generate {
+        match run_generate(when, &format) {
+            Ok(output) => {
+                println!("{}", output);
+                return;
+            }
+            Err(e) => {
+                if !cli.quiet {
+                    eprintln!("Error: {}", e);
+                }
+                std::process::exit(1);
+            }
+        }
+    }
+
     // Collect UUIDs from args or stdin
     let uuid_inputs: Vec<String> = if !cli.uuids.is_empty() {
         cli.uuids.clone()
@@ -157,14 +456,67 @@ fn main() {
     
     // Process each UUID
     let mut had_error = false;
-    for uuid_str in uuid_inputs {
-        match process_uuid(&uuid_str, &format) {
-            Ok(output) => println!("{}", output),
-            Err(e) => {
-                if !cli.quiet {
-                    eprintln!("Error: {}", e);
+
+    if cli.sort && !cli.inspect {
+        // Buffer every entry so they can be reordered by timestamp before printing
+        let mut entries: Vec<(String, i64)> = Vec::new();
+        for uuid_str in &uuid_inputs {
+            let parsed = Uuid::parse_str(uuid_str.trim())
+                .map_err(|e| format!("Invalid UUID: {}", e))
+                .and_then(|uuid| extract_timestamp_ms(&uuid));
+            match parsed {
+                Ok(ts_ms) => entries.push((uuid_str.trim().to_string(), ts_ms)),
+                Err(e) => {
+                    if !cli.quiet {
+                        eprintln!("Error: {}", e);
+                    }
+                    had_error = true;
+                }
+            }
+        }
+
+        entries.sort_by_key(|(_, ts_ms)| *ts_ms);
+        if cli.reverse {
+            entries.reverse();
+        }
+
+        let mut outputs: Vec<String> = Vec::new();
+        for (uuid_str, ts_ms) in entries {
+            match format_timestamp(&uuid_str, ts_ms, &format, tz, cli.relative) {
+                Ok(output) => outputs.push(output),
+                Err(e) => {
+                    if !cli.quiet {
+                        eprintln!("Error: {}", e);
+                    }
+                    had_error = true;
+                }
+            }
+        }
+
+        if format == OutputFormat::Json {
+            // Each entry is already a serialized JSON object; combine them into
+            // a single scriptable array rather than printing one object per line.
+            println!("[{}]", outputs.join(","));
+        } else {
+            for output in outputs {
+                println!("{}", output);
+            }
+        }
+    } else {
+        for uuid_str in uuid_inputs {
+            let result = if cli.inspect {
+                inspect_uuid(&uuid_str, &format)
+            } else {
+                process_uuid(&uuid_str, &format, tz, cli.relative)
+            };
+            match result {
+                Ok(output) => println!("{}", output),
+                Err(e) => {
+                    if !cli.quiet {
+                        eprintln!("Error: {}", e);
+                    }
+                    had_error = true;
                 }
-                had_error = true;
             }
         }
     }